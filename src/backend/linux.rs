@@ -0,0 +1,265 @@
+use super::AuthError;
+use crate::credential::Credential;
+use crate::network::{Network, Security};
+use std::io;
+use std::process::Command;
+
+pub struct NmcliBackend;
+
+impl super::WifiBackend for NmcliBackend {
+    fn scan(&self) -> io::Result<Vec<Network>> {
+        let output = Command::new("nmcli")
+            .args([
+                "-t",
+                "-f",
+                "IN-USE,BSSID,SSID,CHAN,SIGNAL,SECURITY",
+                "device",
+                "wifi",
+                "list",
+            ])
+            .output()?;
+
+        Ok(parse_nmcli_terminal(
+            &String::from_utf8(output.stdout).unwrap(),
+        ))
+    }
+
+    fn connect(&self, ssid: &str, credential: &Credential) -> Result<String, AuthError> {
+        match credential {
+            Credential::Open => run_nmcli(&["device", "wifi", "connect", ssid]),
+            Credential::Wep(key) | Credential::WpaPsk(key) => {
+                run_nmcli(&["device", "wifi", "connect", ssid, "password", key])
+            }
+            Credential::Enterprise { identity, password } => {
+                run_nmcli(&[
+                    "connection",
+                    "add",
+                    "type",
+                    "wifi",
+                    "con-name",
+                    ssid,
+                    "ifname",
+                    "*",
+                    "ssid",
+                    ssid,
+                    "wifi-sec.key-mgmt",
+                    "wpa-eap",
+                    "802-1x.eap",
+                    "peap",
+                    "802-1x.identity",
+                    identity,
+                    "802-1x.password",
+                    password,
+                ])?;
+                run_nmcli(&["connection", "up", ssid])
+            }
+        }
+    }
+
+    fn known_networks(&self) -> io::Result<Vec<String>> {
+        let output = Command::new("nmcli")
+            .args(["-t", "-f", "NAME", "connection", "show"])
+            .output()?;
+
+        Ok(String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
+            .collect())
+    }
+
+    fn reconnect(&self, ssid: &str) -> Result<String, AuthError> {
+        run_nmcli(&["connection", "up", ssid])
+    }
+}
+
+fn run_nmcli(args: &[&str]) -> Result<String, AuthError> {
+    let output = Command::new("nmcli")
+        .args(args)
+        .output()
+        .map_err(|e| AuthError::Program(Box::new(e)))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8(output.stdout).unwrap())
+    } else {
+        Err(AuthError::Command(
+            String::from_utf8(output.stderr).unwrap(),
+        ))
+    }
+}
+
+// `nmcli -t -f ... device wifi list` emits one colon-separated record per
+// line, escaping literal colons and backslashes within a field as `\:` and
+// `\\`. Splitting naively on `:` would break on SSIDs/BSSIDs containing one.
+fn parse_nmcli_terminal(text: &str) -> Vec<Network> {
+    text.lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let fields = split_escaped(line);
+            let in_use = fields.first().map(|s| s == "*").unwrap_or(false);
+            let bssid = fields.get(1).filter(|s| !s.is_empty()).cloned();
+            let ssid = fields.get(2).cloned().unwrap_or_default();
+            let channel = fields.get(3).and_then(|s| s.parse().ok());
+            let signal = fields.get(4).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let security = fields
+                .get(5)
+                .map(|s| Security::classify(s))
+                .unwrap_or(Security::Open);
+
+            Network {
+                ssid,
+                bssid,
+                signal,
+                channel,
+                security,
+                in_use,
+            }
+        })
+        .collect()
+}
+
+fn split_escaped(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&escaped) = chars.peek() {
+                current.push(escaped);
+                chars.next();
+                continue;
+            }
+        }
+        if c == ':' {
+            fields.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    fields.push(current);
+
+    fields
+}
+
+/// Fallback for boxes without NetworkManager: parses `iw dev <iface> scan`.
+pub struct IwBackend {
+    pub iface: String,
+}
+
+impl super::WifiBackend for IwBackend {
+    fn scan(&self) -> io::Result<Vec<Network>> {
+        let output = Command::new("iw")
+            .args(["dev", &self.iface, "scan"])
+            .output()?;
+
+        Ok(parse_iw_scan(&String::from_utf8(output.stdout).unwrap()))
+    }
+
+    fn connect(&self, _ssid: &str, _credential: &Credential) -> Result<String, AuthError> {
+        Err(AuthError::Command(
+            "connecting via `iw` is not supported; install NetworkManager (nmcli) to authenticate"
+                .to_string(),
+        ))
+    }
+}
+
+fn parse_iw_scan(text: &str) -> Vec<Network> {
+    let mut networks = Vec::new();
+    let mut current: Option<Network> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("BSS ") {
+            if let Some(net) = current.take() {
+                networks.push(net);
+            }
+            let bssid = rest.split(['(', ' ']).next().unwrap_or("").to_string();
+            current = Some(Network {
+                ssid: String::new(),
+                bssid: Some(bssid),
+                signal: 0,
+                channel: None,
+                security: Security::Open,
+                in_use: false,
+            });
+        } else if let Some(net) = current.as_mut() {
+            if let Some(ssid) = line.strip_prefix("SSID: ") {
+                net.ssid = ssid.to_string();
+            } else if let Some(sig) = line.strip_prefix("signal: ") {
+                if let Some(dbm) = sig.split_whitespace().next().and_then(|s| s.parse::<f32>().ok()) {
+                    net.signal = dbm_to_percent(dbm);
+                }
+            } else if let Some(freq) = line.strip_prefix("freq: ") {
+                if let Ok(f) = freq.trim().parse::<u32>() {
+                    net.channel = Some(freq_to_channel(f));
+                }
+            } else if line.starts_with("RSN:") || line.starts_with("WPA:") {
+                net.security = Security::classify(line);
+            }
+        }
+    }
+    if let Some(net) = current.take() {
+        networks.push(net);
+    }
+
+    networks
+}
+
+fn dbm_to_percent(dbm: f32) -> u8 {
+    let clamped = dbm.clamp(-100.0, -50.0);
+    (((clamped + 100.0) / 50.0) * 100.0) as u8
+}
+
+fn freq_to_channel(freq: u32) -> u16 {
+    match freq {
+        2412..=2484 => ((freq - 2407) / 5) as u16,
+        5000..=5900 => ((freq - 5000) / 5) as u16,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_escaped_unescapes_colons_in_a_field() {
+        let fields = split_escaped(r"*:aa\:bb\:cc\:dd\:ee\:ff:Home:6:80:WPA2");
+        assert_eq!(
+            fields,
+            vec!["*", "aa:bb:cc:dd:ee:ff", "Home", "6", "80", "WPA2"]
+        );
+    }
+
+    #[test]
+    fn split_escaped_unescapes_colons_in_an_ssid() {
+        let fields = split_escaped(r":aa:bb\:cc:6:80:--");
+        assert_eq!(fields[2], "bb:cc");
+    }
+
+    #[test]
+    fn parse_nmcli_terminal_builds_one_network_per_line() {
+        let text = concat!(
+            r"*:aa\:bb\:cc\:dd\:ee\:ff:Home:6:80:WPA2",
+            "\n",
+            r":11\:22\:33\:44\:55\:66:Guest\:Net:11:40:--",
+            "\n",
+        );
+
+        let networks = parse_nmcli_terminal(text);
+
+        assert_eq!(networks.len(), 2);
+        assert!(networks[0].in_use);
+        assert_eq!(networks[0].ssid, "Home");
+        assert_eq!(networks[0].bssid.as_deref(), Some("aa:bb:cc:dd:ee:ff"));
+        assert_eq!(networks[0].channel, Some(6));
+        assert_eq!(networks[0].signal, 80);
+        assert_eq!(networks[0].security, Security::WpaPsk);
+
+        assert!(!networks[1].in_use);
+        assert_eq!(networks[1].ssid, "Guest:Net");
+        assert_eq!(networks[1].security, Security::Open);
+    }
+}
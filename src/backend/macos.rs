@@ -0,0 +1,137 @@
+use super::AuthError;
+use crate::credential::Credential;
+use crate::network::{Network, Security};
+use std::io;
+use std::process::Command;
+
+const AIRPORT_PATH: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+/// Recent macOS releases ship without the private `airport` binary, so this
+/// is checked for directly rather than probed via `which` like other tools.
+pub fn airport_exists() -> bool {
+    std::path::Path::new(AIRPORT_PATH).exists()
+}
+
+pub struct AirportBackend;
+
+impl super::WifiBackend for AirportBackend {
+    fn scan(&self) -> io::Result<Vec<Network>> {
+        let output = Command::new(AIRPORT_PATH).arg("-s").output()?;
+
+        Ok(parse_airport_table(&String::from_utf8(output.stdout).unwrap()))
+    }
+
+    fn connect(&self, ssid: &str, credential: &Credential) -> Result<String, AuthError> {
+        let mut args = vec!["-setairportnetwork", "en0", ssid];
+        match credential {
+            Credential::Open => {}
+            Credential::Wep(key) | Credential::WpaPsk(key) => args.push(key),
+            Credential::Enterprise { .. } => {
+                return Err(AuthError::Command(
+                    "networksetup cannot join 802.1X enterprise networks from the command line; connect via System Settings".to_string(),
+                ))
+            }
+        }
+
+        let output = Command::new("networksetup")
+            .args(&args)
+            .output()
+            .map_err(|e| AuthError::Program(Box::new(e)))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8(output.stdout).unwrap())
+        } else {
+            Err(AuthError::Command(
+                String::from_utf8(output.stderr).unwrap(),
+            ))
+        }
+    }
+}
+
+// `airport -s` prints a whitespace-aligned table:
+// SSID BSSID             RSSI CHANNEL HT CC SECURITY
+fn parse_airport_table(text: &str) -> Vec<Network> {
+    let mut lines = text.lines();
+    let header = match lines.next() {
+        Some(h) => h,
+        None => return Vec::new(),
+    };
+
+    let bssid_col = header.find("BSSID").unwrap_or(0);
+    let rssi_col = header.find("RSSI").unwrap_or(usize::MAX);
+    let chan_col = header.find("CHANNEL").unwrap_or(usize::MAX);
+    let security_col = header.find("SECURITY").unwrap_or(usize::MAX);
+
+    lines
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let ssid = l.get(..bssid_col).unwrap_or("").trim().to_string();
+            let bssid = l
+                .get(bssid_col..rssi_col.min(l.len()))
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+            let rssi: i32 = l
+                .get(rssi_col..chan_col.min(l.len()))
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(-100);
+            let channel = l
+                .get(chan_col..security_col.min(l.len()))
+                .and_then(|s| s.trim().split(',').next())
+                .and_then(|s| s.trim().parse().ok());
+            let security = Security::classify(l.get(security_col..).unwrap_or("NONE").trim());
+
+            Network {
+                ssid,
+                bssid,
+                signal: rssi_to_percent(rssi),
+                channel,
+                security,
+                in_use: false,
+            }
+        })
+        .collect()
+}
+
+fn rssi_to_percent(rssi: i32) -> u8 {
+    let clamped = rssi.clamp(-100, -50);
+    (((clamped + 100) as f32 / 50.0) * 100.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_one_network_per_row() {
+        let text = concat!(
+            "SSID BSSID             RSSI CHANNEL HT CC SECURITY\n",
+            "Home aa:bb:cc:dd:ee:ff -50  36            WPA2(PSK/AES,AES)\n",
+            "Guest11:22:33:44:55:66 -80  11            NONE\n",
+        );
+
+        let networks = parse_airport_table(text);
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "Home");
+        assert_eq!(networks[0].bssid.as_deref(), Some("aa:bb:cc:dd:ee:ff"));
+        assert_eq!(networks[0].channel, Some(36));
+        assert_eq!(networks[0].security, Security::WpaPsk);
+        assert_eq!(networks[0].signal, rssi_to_percent(-50));
+
+        assert_eq!(networks[1].ssid, "Guest");
+        assert_eq!(networks[1].security, Security::Open);
+    }
+
+    #[test]
+    fn returns_empty_for_header_only_output() {
+        let text = "SSID BSSID             RSSI CHANNEL HT CC SECURITY\n";
+        assert!(parse_airport_table(text).is_empty());
+    }
+
+    #[test]
+    fn rssi_to_percent_clamps_to_0_100_range() {
+        assert_eq!(rssi_to_percent(-120), 0);
+        assert_eq!(rssi_to_percent(-30), 100);
+    }
+}
@@ -0,0 +1,112 @@
+mod linux;
+mod macos;
+mod windows;
+
+use crate::credential::Credential;
+use crate::network::Network;
+use std::fmt;
+use std::io;
+use std::process::Command;
+
+#[derive(Debug)]
+pub enum AuthError {
+    Program(Box<dyn std::error::Error>),
+    Command(String),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Program(inner) => {
+                write!(f,
+                       "The program encountered the following generic error of type Box<dyn std::error::Error>:\n{}",
+                       inner)
+            }
+            AuthError::Command(inner) => {
+                write!(f,
+                       "The output authentication command (type std::process::Output) threw the following error on execution:\n{}",
+                       inner)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// A platform-specific way of listing and joining wireless networks.
+///
+/// `main()` only ever talks to this trait, so it stays usable regardless of
+/// which tool (or OS) is actually driving the wifi radio underneath.
+pub trait WifiBackend {
+    fn scan(&self) -> io::Result<Vec<Network>>;
+    fn connect(&self, ssid: &str, credential: &Credential) -> Result<String, AuthError>;
+
+    /// SSIDs with a previously-saved connection profile, for backends that
+    /// keep one. Empty by default for backends with no such concept.
+    fn known_networks(&self) -> io::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Rejoins a network via its saved profile, without a fresh credential.
+    /// Only meaningful for backends that override `known_networks`.
+    fn reconnect(&self, ssid: &str) -> Result<String, AuthError> {
+        Err(AuthError::Command(format!(
+            "no saved profile support for {}",
+            ssid
+        )))
+    }
+}
+
+/// Picks a backend by probing which platform tool is actually present.
+pub fn detect_backend() -> Box<dyn WifiBackend> {
+    if cfg!(target_os = "macos") {
+        if macos::airport_exists() {
+            Box::new(macos::AirportBackend)
+        } else {
+            Box::new(UnsupportedBackend { tool: "airport" })
+        }
+    } else if cfg!(target_os = "windows") {
+        if binary_exists("netsh") {
+            Box::new(windows::NetshBackend)
+        } else {
+            Box::new(UnsupportedBackend { tool: "netsh" })
+        }
+    } else if binary_exists("nmcli") {
+        Box::new(linux::NmcliBackend)
+    } else if binary_exists("iw") {
+        Box::new(linux::IwBackend {
+            iface: "wlan0".to_string(),
+        })
+    } else {
+        Box::new(UnsupportedBackend { tool: "nmcli or iw" })
+    }
+}
+
+fn binary_exists(bin: &str) -> bool {
+    let finder = if cfg!(windows) { "where" } else { "which" };
+    Command::new(finder)
+        .arg(bin)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Used when no platform wifi tool can be found at startup, so the app can
+/// still launch (with an empty network list) instead of a scan failing with
+/// a raw `io::Error` the first time it runs.
+struct UnsupportedBackend {
+    tool: &'static str,
+}
+
+impl WifiBackend for UnsupportedBackend {
+    fn scan(&self) -> io::Result<Vec<Network>> {
+        Ok(Vec::new())
+    }
+
+    fn connect(&self, _ssid: &str, _credential: &Credential) -> Result<String, AuthError> {
+        Err(AuthError::Command(format!(
+            "no supported wifi tool found ({} is missing); install it and restart",
+            self.tool
+        )))
+    }
+}
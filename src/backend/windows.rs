@@ -0,0 +1,180 @@
+use super::AuthError;
+use crate::credential::Credential;
+use crate::network::{Network, Security};
+use std::io;
+use std::process::Command;
+
+pub struct NetshBackend;
+
+impl super::WifiBackend for NetshBackend {
+    fn scan(&self) -> io::Result<Vec<Network>> {
+        let output = Command::new("netsh")
+            .args(["wlan", "show", "networks", "mode=bssid"])
+            .output()?;
+
+        Ok(parse_netsh_blocks(&String::from_utf8(output.stdout).unwrap()))
+    }
+
+    fn connect(&self, ssid: &str, credential: &Credential) -> Result<String, AuthError> {
+        if let Credential::Enterprise { .. } = credential {
+            return Err(AuthError::Command(
+                "connecting to 802.1X enterprise networks requires a saved EAP profile; create one in Settings first".to_string(),
+            ));
+        }
+        // `netsh` only connects to networks that already have a saved
+        // profile; the credential is accepted here to match the trait but
+        // is unused until profile creation is implemented.
+        let output = Command::new("netsh")
+            .args(["wlan", "connect", &format!("name={}", ssid)])
+            .output()
+            .map_err(|e| AuthError::Program(Box::new(e)))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8(output.stdout).unwrap())
+        } else {
+            Err(AuthError::Command(
+                String::from_utf8(output.stderr).unwrap(),
+            ))
+        }
+    }
+}
+
+// `netsh wlan show networks mode=bssid` prints indented key/value blocks, one
+// per SSID, each nesting one "BSSID N :" sub-block per access point sharing
+// that SSID:
+// SSID 1 : MyNetwork
+//     Authentication : WPA2-Personal
+//     BSSID 1        : aa:bb:cc:dd:ee:ff
+//          Signal    : 80%
+//          Channel   : 36
+//     BSSID 2        : 11:22:33:44:55:66
+//          Signal    : 45%
+//          Channel   : 100
+// `Authentication` is reported once per SSID, but `Signal`/`Channel` belong to
+// whichever `BSSID` sub-block they're nested under, so each BSSID becomes its
+// own `Network` row (like the `iw`/`nmcli` backends do).
+fn parse_netsh_blocks(text: &str) -> Vec<Network> {
+    let mut networks = Vec::new();
+    let mut ssid = String::new();
+    let mut security = Security::Open;
+    let mut current: Option<Network> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("SSID ") {
+            if let Some(net) = current.take() {
+                networks.push(net);
+            }
+            ssid = rest.split_once(':').map(|(_, v)| v).unwrap_or("").trim().to_string();
+            security = Security::Open;
+        } else if let Some(val) = value_after(line, "BSSID") {
+            if let Some(net) = current.take() {
+                networks.push(net);
+            }
+            current = Some(Network {
+                ssid: ssid.clone(),
+                bssid: Some(val),
+                signal: 0,
+                channel: None,
+                security,
+                in_use: false,
+            });
+        } else if let Some(val) = value_after(line, "Authentication") {
+            security = Security::classify(&val);
+            if let Some(net) = current.as_mut() {
+                net.security = security;
+            }
+        } else if let Some(net) = current.as_mut() {
+            if let Some(val) = value_after(line, "Signal") {
+                let signal: u32 = val.trim_end_matches('%').parse().unwrap_or(0);
+                net.signal = signal.min(100) as u8;
+            } else if let Some(val) = value_after(line, "Channel") {
+                net.channel = val.parse().ok();
+            }
+        }
+    }
+    if let Some(net) = current.take() {
+        networks.push(net);
+    }
+
+    networks
+}
+
+fn value_after(line: &str, key: &str) -> Option<String> {
+    if line.starts_with(key) {
+        line.split_once(':').map(|(_, v)| v.trim().to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_one_network_per_bssid_under_the_same_ssid() {
+        let text = concat!(
+            "SSID 1 : MyNetwork\n",
+            "    Authentication : WPA2-Personal\n",
+            "    BSSID 1        : aa:bb:cc:dd:ee:ff\n",
+            "         Signal    : 80%\n",
+            "         Channel   : 36\n",
+            "    BSSID 2        : 11:22:33:44:55:66\n",
+            "         Signal    : 45%\n",
+            "         Channel   : 100\n",
+        );
+
+        let networks = parse_netsh_blocks(text);
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "MyNetwork");
+        assert_eq!(networks[0].bssid.as_deref(), Some("aa:bb:cc:dd:ee:ff"));
+        assert_eq!(networks[0].signal, 80);
+        assert_eq!(networks[0].channel, Some(36));
+        assert_eq!(networks[0].security, Security::WpaPsk);
+
+        assert_eq!(networks[1].ssid, "MyNetwork");
+        assert_eq!(networks[1].bssid.as_deref(), Some("11:22:33:44:55:66"));
+        assert_eq!(networks[1].signal, 45);
+        assert_eq!(networks[1].channel, Some(100));
+        assert_eq!(networks[1].security, Security::WpaPsk);
+    }
+
+    #[test]
+    fn parses_multiple_ssid_blocks() {
+        let text = concat!(
+            "SSID 1 : Home\n",
+            "    Authentication : WPA2-Personal\n",
+            "    BSSID 1        : aa:bb:cc:dd:ee:ff\n",
+            "         Signal    : 80%\n",
+            "         Channel   : 6\n",
+            "SSID 2 : Guest\n",
+            "    Authentication : Open\n",
+            "    BSSID 1        : 11:22:33:44:55:66\n",
+            "         Signal    : 30%\n",
+            "         Channel   : 11\n",
+        );
+
+        let networks = parse_netsh_blocks(text);
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "Home");
+        assert_eq!(networks[1].ssid, "Guest");
+        assert_eq!(networks[1].security, Security::Open);
+    }
+
+    #[test]
+    fn clamps_signal_over_100_percent() {
+        let text = concat!(
+            "SSID 1 : Weird\n",
+            "    Authentication : Open\n",
+            "    BSSID 1        : aa:bb:cc:dd:ee:ff\n",
+            "         Signal    : 150%\n",
+        );
+
+        let networks = parse_netsh_blocks(text);
+
+        assert_eq!(networks[0].signal, 100);
+    }
+}
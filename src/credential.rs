@@ -0,0 +1,96 @@
+use std::fmt;
+
+/// The secret needed to join a network, shaped by its `Security`.
+#[derive(Debug, Clone)]
+pub enum Credential {
+    Open,
+    Wep(String),
+    WpaPsk(String),
+    Enterprise { identity: String, password: String },
+}
+
+#[derive(Debug)]
+pub enum CredentialError {
+    InvalidWepKey,
+    InvalidWpaPassphrase,
+}
+
+impl fmt::Display for CredentialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialError::InvalidWepKey => write!(
+                f,
+                "WEP keys must be 5 or 13 ASCII characters, or 10 or 26 hex characters"
+            ),
+            CredentialError::InvalidWpaPassphrase => write!(
+                f,
+                "WPA passphrases must be 8-63 characters, or a 64-character hex PSK"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CredentialError {}
+
+impl Credential {
+    pub fn wep(key: &str) -> Result<Credential, CredentialError> {
+        let is_ascii_key = matches!(key.len(), 5 | 13) && key.is_ascii();
+        let is_hex_key = matches!(key.len(), 10 | 26) && key.chars().all(|c| c.is_ascii_hexdigit());
+        if is_ascii_key || is_hex_key {
+            Ok(Credential::Wep(key.to_string()))
+        } else {
+            Err(CredentialError::InvalidWepKey)
+        }
+    }
+
+    pub fn wpa_psk(passphrase: &str) -> Result<Credential, CredentialError> {
+        let is_raw_psk =
+            passphrase.len() == 64 && passphrase.chars().all(|c| c.is_ascii_hexdigit());
+        let is_passphrase = (8..=63).contains(&passphrase.len());
+        if is_raw_psk || is_passphrase {
+            Ok(Credential::WpaPsk(passphrase.to_string()))
+        } else {
+            Err(CredentialError::InvalidWpaPassphrase)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wep_accepts_5_or_13_ascii_chars() {
+        assert!(Credential::wep("abcde").is_ok());
+        assert!(Credential::wep("abcdefghijklm").is_ok());
+    }
+
+    #[test]
+    fn wep_accepts_10_or_26_hex_chars() {
+        assert!(Credential::wep("0123456789").is_ok());
+        assert!(Credential::wep(&"a".repeat(26)).is_ok());
+    }
+
+    #[test]
+    fn wep_rejects_other_lengths() {
+        assert!(Credential::wep("nope").is_err());
+        assert!(Credential::wep("sixchr").is_err());
+    }
+
+    #[test]
+    fn wpa_psk_accepts_8_to_63_char_passphrase() {
+        assert!(Credential::wpa_psk("password").is_ok());
+        assert!(Credential::wpa_psk(&"a".repeat(63)).is_ok());
+    }
+
+    #[test]
+    fn wpa_psk_accepts_64_char_hex_psk() {
+        assert!(Credential::wpa_psk(&"a".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn wpa_psk_rejects_too_short_or_too_long() {
+        assert!(Credential::wpa_psk("short12").is_err());
+        assert!(Credential::wpa_psk(&"a".repeat(65)).is_err());
+    }
+}
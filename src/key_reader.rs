@@ -0,0 +1,19 @@
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use termion::event::Event;
+use termion::input::TermRead;
+
+/// Reads key events off stdin on a background thread and forwards them over
+/// a channel, so the main loop can poll for input instead of blocking on it.
+pub fn spawn() -> Receiver<Event> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = std::io::stdin();
+        for evt in stdin.events().flatten() {
+            if tx.send(evt).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
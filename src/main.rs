@@ -1,89 +1,235 @@
 extern crate termion;
 
-use std::io::{self, stdin, stdout, Stdout, Write};
-use std::process::Command;
-use std::u8;
+mod backend;
+mod credential;
+mod key_reader;
+mod network;
+
+use backend::detect_backend;
+use credential::Credential;
+use network::{Network, Security};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, stdout, Stdout, Write};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
 use termion::event::{Event, Key};
-use termion::input::TermRead;
 use termion::raw::{IntoRawMode, RawTerminal};
 
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
 enum Response {
     Continue,
     Select,
+    Reconnect,
+    ToggleSort,
+    ToggleDedupe,
+    StartFilter,
     Quit,
 }
 
-#[derive(Debug)]
-enum AuthError {
-    Program(Box<dyn std::error::Error>),
-    Command(String),
+/// What to send to the backend once a credential (or saved profile) is ready.
+enum ConnectAction {
+    New(Credential),
+    Saved,
 }
 
-impl std::fmt::Display for AuthError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AuthError::Program(inner) => {
-                write!(f,
-                       "The program encountered the following generic error of type Box<dyn std::error::Error>:\n{}",
-                       inner)
-            }
-            AuthError::Command(inner) => {
-                write!(f,
-                       "The output authentication command (type std::process::Output) threw the following error on execution:\n{}",
-                       inner)
+/// The field(s) still needed to build a `Credential` for the selected
+/// network's `Security`, in the order they should be prompted.
+fn prompts_for(security: Security) -> Vec<&'static str> {
+    match security {
+        Security::Open => vec![],
+        Security::Wep => vec!["WEP key"],
+        Security::WpaPsk => vec!["WPA passphrase"],
+        Security::Wpa2Enterprise => vec!["Identity", "Password"],
+    }
+}
+
+fn build_credential(security: Security, answers: &[String]) -> Result<Credential, String> {
+    match security {
+        Security::Open => Ok(Credential::Open),
+        Security::Wep => Credential::wep(&answers[0]).map_err(|e| e.to_string()),
+        Security::WpaPsk => Credential::wpa_psk(&answers[0]).map_err(|e| e.to_string()),
+        Security::Wpa2Enterprise => Ok(Credential::Enterprise {
+            identity: answers[0].clone(),
+            password: answers[1].clone(),
+        }),
+    }
+}
+
+/// Applies the user's filter/dedupe/sort toggles to a freshly scanned list.
+fn compute_view(
+    networks: &[Network],
+    filter: &str,
+    hide_duplicates: bool,
+    sort_by_signal: bool,
+) -> Vec<Network> {
+    let mut view: Vec<Network> = networks
+        .iter()
+        .filter(|n| {
+            filter.is_empty() || n.ssid.to_lowercase().contains(&filter.to_lowercase())
+        })
+        .cloned()
+        .collect();
+
+    if hide_duplicates {
+        view = dedupe_strongest(view);
+    }
+
+    if sort_by_signal {
+        view.sort_by_key(|n| std::cmp::Reverse(n.signal));
+    }
+
+    view
+}
+
+/// Keeps only the strongest-signal entry per SSID, for networks broadcast by
+/// more than one access point (same SSID, different BSSID).
+fn dedupe_strongest(networks: Vec<Network>) -> Vec<Network> {
+    let mut seen_bssids: HashSet<String> = HashSet::new();
+    let mut by_ssid: HashMap<String, Network> = HashMap::new();
+    for net in networks {
+        if let Some(bssid) = &net.bssid {
+            // Some backends (e.g. a busy `iw` scan) occasionally report the
+            // same access point twice in one pass; only count it once.
+            if !seen_bssids.insert(bssid.clone()) {
+                continue;
             }
         }
+        by_ssid
+            .entry(net.ssid.clone())
+            .and_modify(|strongest| {
+                if net.signal > strongest.signal {
+                    *strongest = net.clone();
+                }
+            })
+            .or_insert(net);
     }
-}
 
-impl std::error::Error for AuthError {}
+    let mut deduped: Vec<Network> = by_ssid.into_values().collect();
+    deduped.sort_by(|a, b| a.ssid.cmp(&b.ssid));
+    deduped
+}
 
 fn main() -> io::Result<()> {
-    let stdin = stdin();
     let mut stdout = stdout().into_raw_mode()?;
+    let backend = detect_backend();
+    let key_events = key_reader::spawn();
 
     let mut selector_pos: u8 = 0;
-    let mut wifi_list: String = refresh(&mut stdout, &selector_pos)?;
+    let mut networks: Vec<Network> = backend.scan()?;
+    let mut sort_by_signal = false;
+    let mut hide_duplicates = false;
+    let mut filter = String::new();
+    let mut view = compute_view(&networks, &filter, hide_duplicates, sort_by_signal);
+    draw_list(&mut stdout, &view, selector_pos)?;
 
     let mut state = 0;
-    let mut pwd = String::new();
     let mut ssid = String::new();
+    let mut security = Security::Open;
+    let mut prompts: Vec<&'static str> = Vec::new();
+    let mut answers: Vec<String> = Vec::new();
+    let mut buf = String::new();
+    let mut cursor: usize = 0;
+    let mut action: Option<ConnectAction> = None;
 
-    let mut event_iter = stdin.events();
     loop {
         match state {
             0 => {
-                if let Some(Ok(evt)) = event_iter.next() {
-                    let response = match_evt(evt, &mut stdout, &wifi_list, &mut selector_pos)?;
-                    match response {
-                        Response::Continue => wifi_list = refresh(&mut stdout, &selector_pos)?,
-                        Response::Select => {
-                            state = 1;
-                            ssid = get_ssids(&wifi_list)[selector_pos as usize].clone();
+                // Select blocks on either a keypress or the refresh timer, so
+                // the list keeps scanning while the user is just looking at it.
+                match key_events.recv_timeout(REFRESH_INTERVAL) {
+                    Ok(evt) => {
+                        let response = match_evt(evt, &view, &mut selector_pos)?;
+                        match response {
+                            Response::Continue => {}
+                            Response::ToggleSort => sort_by_signal = !sort_by_signal,
+                            Response::ToggleDedupe => hide_duplicates = !hide_duplicates,
+                            Response::StartFilter => state = 3,
+                            Response::Select => {
+                                if let Some(net) = view.get(selector_pos as usize).cloned() {
+                                    begin_credential_flow(
+                                        &net, &mut ssid, &mut security, &mut prompts,
+                                        &mut answers, &mut buf, &mut cursor, &mut action,
+                                        &mut state,
+                                    );
+                                }
+                            }
+                            Response::Reconnect => {
+                                if let Some(net) = view.get(selector_pos as usize).cloned() {
+                                    let known = backend.known_networks()?;
+                                    if known.iter().any(|name| name == &net.ssid) {
+                                        ssid = net.ssid.clone();
+                                        action = Some(ConnectAction::Saved);
+                                        state = 2;
+                                    } else {
+                                        begin_credential_flow(
+                                            &net, &mut ssid, &mut security, &mut prompts,
+                                            &mut answers, &mut buf, &mut cursor, &mut action,
+                                            &mut state,
+                                        );
+                                    }
+                                }
+                            }
+                            Response::Quit => break,
+                        }
+                        if state == 0 {
+                            view = compute_view(&networks, &filter, hide_duplicates, sort_by_signal);
+                            selector_pos = selector_pos.min(view.len().saturating_sub(1) as u8);
+                            draw_list(&mut stdout, &view, selector_pos)?;
                         }
-                        Response::Quit => break,
                     }
+                    Err(RecvTimeoutError::Timeout) => {
+                        networks = backend.scan()?;
+                        view = compute_view(&networks, &filter, hide_duplicates, sort_by_signal);
+                        selector_pos = selector_pos.min(view.len().saturating_sub(1) as u8);
+                        draw_list(&mut stdout, &view, selector_pos)?;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
                 }
             }
             1 => {
-                clear_and_write(
-                    &mut stdout,
-                    &format!("CONNECTING\nSSID: {}\nPassword: ", &ssid),
-                )?;
-                RawTerminal::suspend_raw_mode(&stdout)?;
-                if let Some(Ok(evt)) = event_iter.next() {
-                    match evt {
-                        Event::Key(Key::Esc) => break,
-                        Event::Key(Key::Char(c)) => match c {
-                            '\n' => state = 2,
-                            _ => pwd.push(c),
-                        },
-                        _ => {}
+                let label = prompts[answers.len()];
+                draw_password_prompt(&mut stdout, &ssid, label, &buf, cursor)?;
+                match key_events.recv() {
+                    Ok(Event::Key(Key::Esc)) => break,
+                    Ok(Event::Key(Key::Char('\n'))) => {
+                        answers.push(std::mem::take(&mut buf));
+                        cursor = 0;
+                        if answers.len() == prompts.len() {
+                            match build_credential(security, &answers) {
+                                Ok(cred) => {
+                                    action = Some(ConnectAction::New(cred));
+                                    state = 2;
+                                }
+                                Err(msg) => {
+                                    clear_and_write(&mut stdout, &msg)?;
+                                    break;
+                                }
+                            }
+                        }
                     }
+                    Ok(Event::Key(Key::Char(c))) => {
+                        buf.insert(char_byte_offset(&buf, cursor), c);
+                        cursor += 1;
+                    }
+                    Ok(Event::Key(Key::Backspace)) => {
+                        if cursor > 0 {
+                            cursor -= 1;
+                            buf.remove(char_byte_offset(&buf, cursor));
+                        }
+                    }
+                    Ok(Event::Key(Key::Left)) => cursor = cursor.saturating_sub(1),
+                    Ok(Event::Key(Key::Right)) => cursor = (cursor + 1).min(buf.chars().count()),
+                    Ok(_) => {}
+                    Err(_) => break,
                 }
             }
             2 => {
-                match authenticate(&ssid, &pwd) {
+                let result = match action.as_ref().expect("action set before state 2") {
+                    ConnectAction::New(cred) => backend.connect(&ssid, cred),
+                    ConnectAction::Saved => backend.reconnect(&ssid),
+                };
+                match result {
                     Ok(msg) => {
                         clear_and_write(
                             &mut stdout,
@@ -94,6 +240,24 @@ fn main() -> io::Result<()> {
                 }
                 break;
             }
+            3 => {
+                draw_filter_prompt(&mut stdout, &view, selector_pos, &filter)?;
+                match key_events.recv() {
+                    Ok(Event::Key(Key::Esc)) => {
+                        filter.clear();
+                        state = 0;
+                    }
+                    Ok(Event::Key(Key::Char('\n'))) => state = 0,
+                    Ok(Event::Key(Key::Char(c))) => filter.push(c),
+                    Ok(Event::Key(Key::Backspace)) => {
+                        filter.pop();
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+                view = compute_view(&networks, &filter, hide_duplicates, sort_by_signal);
+                selector_pos = selector_pos.min(view.len().saturating_sub(1) as u8);
+            }
             _ => break,
         }
 
@@ -103,12 +267,35 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn match_evt(
-    evt: Event,
-    stdout: &mut RawTerminal<Stdout>,
-    wifi_list: &str,
-    selector_pos: &mut u8,
-) -> io::Result<Response> {
+/// Shared by both "select a network" and "reconnect, but it's unknown": work
+/// out what credential (if any) the next state needs to collect.
+#[allow(clippy::too_many_arguments)]
+fn begin_credential_flow(
+    net: &Network,
+    ssid: &mut String,
+    security: &mut Security,
+    prompts: &mut Vec<&'static str>,
+    answers: &mut Vec<String>,
+    buf: &mut String,
+    cursor: &mut usize,
+    action: &mut Option<ConnectAction>,
+    state: &mut i32,
+) {
+    *ssid = net.ssid.clone();
+    *security = net.security;
+    *prompts = prompts_for(*security);
+    *answers = Vec::new();
+    *buf = String::new();
+    *cursor = 0;
+    if prompts.is_empty() {
+        *action = Some(ConnectAction::New(Credential::Open));
+        *state = 2;
+    } else {
+        *state = 1;
+    }
+}
+
+fn match_evt(evt: Event, view: &[Network], selector_pos: &mut u8) -> io::Result<Response> {
     return match evt {
         // Arrows
         Event::Key(Key::Up) => {
@@ -120,7 +307,7 @@ fn match_evt(
             Ok(Response::Continue)
         }
         Event::Key(Key::Down) => {
-            let bound: u8 = wifi_list.lines().skip(1).fold(0, |acc, _| acc + 1) - 1 as u8;
+            let bound = view.len().saturating_sub(1) as u8;
             *selector_pos = (*selector_pos + 1).clamp(0, bound);
             Ok(Response::Continue)
         }
@@ -128,47 +315,103 @@ fn match_evt(
         Event::Key(Key::Char('r')) => Ok(Response::Continue),
         Event::Key(Key::Char('q')) => Ok(Response::Quit),
         Event::Key(Key::Char('\n')) => Ok(Response::Select),
+        Event::Key(Key::Char('c')) => Ok(Response::Reconnect),
+        Event::Key(Key::Char('s')) => Ok(Response::ToggleSort),
+        Event::Key(Key::Char('h')) => Ok(Response::ToggleDedupe),
+        Event::Key(Key::Char('/')) => Ok(Response::StartFilter),
 
         _ => Ok(Response::Continue),
     };
 }
 
-fn wifi_list() -> io::Result<String> {
-    let output = Command::new("nmcli")
-        .arg("device")
-        .arg("wifi")
-        .arg("list")
-        .output()?;
+fn draw_list(stdout: &mut RawTerminal<Stdout>, view: &[Network], selector_pos: u8) -> io::Result<()> {
+    clear_and_write(stdout, &render_list(view))?;
+    write!(
+        stdout,
+        "{}{}{}",
+        ' ',
+        termion::cursor::Goto(1, selector_pos as u16 + 2),
+        '>',
+    )
+}
 
-    return Ok(String::from_utf8(output.stdout).unwrap());
+/// `cursor` counts characters, not bytes, so it stays meaningful for masked
+/// rendering and `Goto`; this converts it to the byte offset `insert`/`remove`
+/// need, which must land on a char boundary even when `buf` has multi-byte
+/// UTF-8 characters in it.
+fn char_byte_offset(buf: &str, char_idx: usize) -> usize {
+    buf.char_indices()
+        .nth(char_idx)
+        .map(|(i, _)| i)
+        .unwrap_or(buf.len())
 }
 
-fn get_ssids(wifi_list: &str) -> Vec<String> {
-    return wifi_list
-        .lines()
-        .skip(1)
-        .map(|l| {
-            l[8..]
-                .split(' ')
-                .next()
-                .expect("Failed to parse ssid")
-                .to_string()
-        })
-        .collect();
+/// Draws the credential prompt with a masked, cursor-editable input line.
+fn draw_password_prompt(
+    stdout: &mut RawTerminal<Stdout>,
+    ssid: &str,
+    label: &str,
+    buf: &str,
+    cursor: usize,
+) -> io::Result<()> {
+    let prefix = format!("{}: ", label);
+    clear_and_write(
+        stdout,
+        &format!(
+            "CONNECTING\nSSID: {}\n{}{}",
+            ssid,
+            prefix,
+            "*".repeat(buf.chars().count())
+        ),
+    )?;
+    write!(
+        stdout,
+        "{}",
+        termion::cursor::Goto((prefix.chars().count() + cursor + 1) as u16, 3)
+    )?;
+    stdout.flush()
 }
 
-fn refresh(stdout: &mut RawTerminal<Stdout>, selector_pos: &u8) -> io::Result<String> {
-    let wifi_list = wifi_list()?;
-    clear_and_write(stdout, &wifi_list)?;
+fn draw_filter_prompt(
+    stdout: &mut RawTerminal<Stdout>,
+    view: &[Network],
+    selector_pos: u8,
+    filter: &str,
+) -> io::Result<()> {
+    let mut contents = format!("FILTER: {}\n", filter);
+    contents.push_str(&render_list(view));
+    clear_and_write(stdout, &contents)?;
     write!(
         stdout,
         "{}{}{}",
         ' ',
-        termion::cursor::Goto(1, *selector_pos as u16 + 2),
+        termion::cursor::Goto(1, selector_pos as u16 + 3),
         '>',
-    )?;
+    )
+}
 
-    return Ok(wifi_list);
+fn render_list(view: &[Network]) -> String {
+    let mut out = String::from("  SSID                            SIGNAL\n");
+    for net in view {
+        let lock = if net.security == Security::Open {
+            ' '
+        } else {
+            '\u{1F512}'
+        };
+        out.push_str(&format!(
+            "{} {:<30} {} {}\n",
+            if net.in_use { '*' } else { ' ' },
+            net.ssid,
+            signal_bar(net.signal),
+            lock,
+        ));
+    }
+    out
+}
+
+fn signal_bar(signal: u8) -> String {
+    let filled = (signal as usize * 10) / 100;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(10 - filled))
 }
 
 fn clear_and_write(stdout: &mut RawTerminal<Stdout>, contents: &str) -> io::Result<()> {
@@ -188,19 +431,3 @@ fn clear_and_write(stdout: &mut RawTerminal<Stdout>, contents: &str) -> io::Resu
 
     Ok(())
 }
-
-fn authenticate(ssid: &str, pwd: &str) -> Result<String, AuthError> {
-    // Authenticate
-    let output = Command::new("nmcli")
-        .args(["device", "wifi", "connect", ssid, "password", pwd])
-        .output()
-        .map_err(|e| AuthError::Program(Box::new(e)))?;
-
-    return if output.status.success() {
-        Ok(String::from_utf8(output.stdout).unwrap())
-    } else {
-        Err(AuthError::Command(
-            String::from_utf8(output.stderr).unwrap(),
-        ))
-    };
-}
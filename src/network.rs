@@ -0,0 +1,64 @@
+/// A single access point discovered by a scan, normalized across backends.
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub ssid: String,
+    pub bssid: Option<String>,
+    /// Signal strength normalized to a 0-100 percentage.
+    pub signal: u8,
+    pub channel: Option<u16>,
+    pub security: Security,
+    pub in_use: bool,
+}
+
+/// The kind of protection a network advertises, classified from whatever
+/// free-form security string the platform tool reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Security {
+    Open,
+    Wep,
+    WpaPsk,
+    Wpa2Enterprise,
+}
+
+impl Security {
+    pub fn classify(raw: &str) -> Security {
+        let upper = raw.to_uppercase();
+        if upper.contains("802.1X") || upper.contains("ENTERPRISE") || upper.contains("EAP") {
+            Security::Wpa2Enterprise
+        } else if upper.contains("WEP") {
+            Security::Wep
+        } else if upper.contains("WPA") {
+            Security::WpaPsk
+        } else {
+            Security::Open
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_open() {
+        assert_eq!(Security::classify("--"), Security::Open);
+        assert_eq!(Security::classify(""), Security::Open);
+    }
+
+    #[test]
+    fn classifies_wep() {
+        assert_eq!(Security::classify("WEP"), Security::Wep);
+    }
+
+    #[test]
+    fn classifies_wpa_psk() {
+        assert_eq!(Security::classify("WPA2"), Security::WpaPsk);
+        assert_eq!(Security::classify("WPA1 WPA2"), Security::WpaPsk);
+    }
+
+    #[test]
+    fn classifies_enterprise() {
+        assert_eq!(Security::classify("WPA2 802.1X"), Security::Wpa2Enterprise);
+        assert_eq!(Security::classify("WPA2-Enterprise"), Security::Wpa2Enterprise);
+    }
+}